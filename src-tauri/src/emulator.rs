@@ -0,0 +1,383 @@
+//! A minimal server-side ANSI terminal emulator.
+//!
+//! The PTY reader thread feeds raw output through [`TerminalEmulator`] in
+//! addition to forwarding it live via `terminal-output`, so a newly attached
+//! or re-rendered frontend can ask for a full [`TerminalScreen`] snapshot
+//! (visible grid plus scrollback) instead of replaying the whole session.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use vte::{Params, Parser, Perform};
+
+const DEFAULT_SCROLLBACK_LINES: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct CellStyle {
+    pub fg: Option<(u8, u8, u8)>,
+    pub bg: Option<(u8, u8, u8)>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cell {
+    pub text: char,
+    #[serde(flatten)]
+    pub style: CellStyle,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            text: ' ',
+            style: CellStyle::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Line {
+    cells: Vec<Cell>,
+    /// True when this row's last cell wrapped onto the next row, so resize
+    /// reflow knows to rejoin it with the following line before rewrapping.
+    wrapped: bool,
+}
+
+impl Line {
+    fn blank(cols: usize) -> Self {
+        Self {
+            cells: vec![Cell::default(); cols],
+            wrapped: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalScreen {
+    pub cols: usize,
+    pub rows: usize,
+    pub grid: Vec<Vec<Cell>>,
+    pub scrollback: Vec<Vec<Cell>>,
+}
+
+pub struct TerminalEmulator {
+    parser: Parser,
+    cols: usize,
+    rows: usize,
+    grid: Vec<Line>,
+    scrollback: VecDeque<Line>,
+    scrollback_cap: usize,
+    cursor_row: usize,
+    cursor_col: usize,
+    pending_style: CellStyle,
+}
+
+impl TerminalEmulator {
+    pub fn new(rows: u16, cols: u16, scrollback_cap: Option<usize>) -> Self {
+        let rows = rows.max(1) as usize;
+        let cols = cols.max(1) as usize;
+        Self {
+            parser: Parser::new(),
+            cols,
+            rows,
+            grid: (0..rows).map(|_| Line::blank(cols)).collect(),
+            scrollback: VecDeque::new(),
+            scrollback_cap: scrollback_cap.unwrap_or(DEFAULT_SCROLLBACK_LINES),
+            cursor_row: 0,
+            cursor_col: 0,
+            pending_style: CellStyle::default(),
+        }
+    }
+
+    /// Feed a chunk of raw PTY bytes through the ANSI parser, updating the
+    /// grid and scrollback. Call this alongside (not instead of) forwarding
+    /// the raw bytes to the frontend over `terminal-output`.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        // vte::Parser::advance borrows the Perform impl per-byte, so take
+        // the parser out to satisfy the borrow checker while we mutate self.
+        let mut parser = std::mem::replace(&mut self.parser, Parser::new());
+        for &byte in bytes {
+            parser.advance(self, byte);
+        }
+        self.parser = parser;
+    }
+
+    /// Reflow the grid and scrollback to a new terminal size, rewrapping
+    /// logical lines (joined across `wrapped` rows) to the new column count.
+    pub fn resize(&mut self, rows: u16, cols: u16) {
+        let new_rows = rows.max(1) as usize;
+        let new_cols = cols.max(1) as usize;
+        if new_cols == self.cols && new_rows == self.rows {
+            return;
+        }
+
+        let mut logical_lines: Vec<Vec<Cell>> = Vec::new();
+        let mut prev_wrapped = false;
+        for line in self.scrollback.iter().chain(self.grid.iter()) {
+            // A row's `wrapped` flag means *it* continues onto the next
+            // row, so a row only joins the previous logical line when the
+            // line before it set that flag.
+            if prev_wrapped {
+                if let Some(last) = logical_lines.last_mut() {
+                    last.extend(line.cells.iter().cloned());
+                    prev_wrapped = line.wrapped;
+                    continue;
+                }
+            }
+            logical_lines.push(line.cells.clone());
+            prev_wrapped = line.wrapped;
+        }
+
+        let mut rewrapped: Vec<Line> = Vec::new();
+        for logical in logical_lines {
+            if logical.is_empty() {
+                rewrapped.push(Line::blank(new_cols));
+                continue;
+            }
+            let mut chunks = logical.chunks(new_cols).peekable();
+            while let Some(chunk) = chunks.next() {
+                let mut cells = chunk.to_vec();
+                cells.resize(new_cols, Cell::default());
+                rewrapped.push(Line {
+                    cells,
+                    wrapped: chunks.peek().is_some(),
+                });
+            }
+        }
+
+        while rewrapped.len() < new_rows {
+            rewrapped.push(Line::blank(new_cols));
+        }
+
+        let split_at = rewrapped.len().saturating_sub(new_rows);
+        let mut scrollback: VecDeque<Line> = rewrapped.drain(..split_at).collect();
+        while scrollback.len() > self.scrollback_cap {
+            scrollback.pop_front();
+        }
+
+        self.cols = new_cols;
+        self.rows = new_rows;
+        self.scrollback = scrollback;
+        self.grid = rewrapped;
+        self.cursor_row = self.cursor_row.min(self.rows.saturating_sub(1));
+        self.cursor_col = self.cursor_col.min(self.cols.saturating_sub(1));
+    }
+
+    pub fn snapshot(&self, include_scrollback: bool) -> TerminalScreen {
+        TerminalScreen {
+            cols: self.cols,
+            rows: self.rows,
+            grid: self.grid.iter().map(|l| l.cells.clone()).collect(),
+            scrollback: if include_scrollback {
+                self.scrollback.iter().map(|l| l.cells.clone()).collect()
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    fn current_line(&mut self) -> &mut Line {
+        &mut self.grid[self.cursor_row]
+    }
+
+    fn newline(&mut self) {
+        if self.cursor_row + 1 < self.rows {
+            self.cursor_row += 1;
+        } else {
+            let overflowed = self.grid.remove(0);
+            self.scrollback.push_back(overflowed);
+            while self.scrollback.len() > self.scrollback_cap {
+                self.scrollback.pop_front();
+            }
+            self.grid.push(Line::blank(self.cols));
+        }
+    }
+
+    fn erase_in_display(&mut self, mode: u16) {
+        match mode {
+            0 => {
+                let (row, col) = (self.cursor_row, self.cursor_col);
+                for cell in &mut self.grid[row].cells[col..] {
+                    *cell = Cell::default();
+                }
+                for line in &mut self.grid[row + 1..] {
+                    *line = Line::blank(self.cols);
+                }
+            }
+            1 => {
+                let (row, col) = (self.cursor_row, self.cursor_col);
+                for line in &mut self.grid[..row] {
+                    *line = Line::blank(self.cols);
+                }
+                for cell in &mut self.grid[row].cells[..=col.min(self.cols - 1)] {
+                    *cell = Cell::default();
+                }
+            }
+            2 | 3 => {
+                for line in &mut self.grid {
+                    *line = Line::blank(self.cols);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn erase_in_line(&mut self, mode: u16) {
+        let cols = self.cols;
+        let col = self.cursor_col;
+        let line = self.current_line();
+        match mode {
+            0 => {
+                for cell in &mut line.cells[col.min(cols)..] {
+                    *cell = Cell::default();
+                }
+            }
+            1 => {
+                for cell in &mut line.cells[..=col.min(cols - 1)] {
+                    *cell = Cell::default();
+                }
+            }
+            2 => {
+                *line = Line::blank(cols);
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_sgr(&mut self, params: &Params) {
+        let mut iter = params.iter();
+        while let Some(param) = iter.next() {
+            match param.first().copied().unwrap_or(0) {
+                0 => self.pending_style = CellStyle::default(),
+                1 => self.pending_style.bold = true,
+                3 => self.pending_style.italic = true,
+                4 => self.pending_style.underline = true,
+                22 => self.pending_style.bold = false,
+                23 => self.pending_style.italic = false,
+                24 => self.pending_style.underline = false,
+                30..=37 => self.pending_style.fg = Some(ansi_16_color(param[0] as u8 - 30)),
+                39 => self.pending_style.fg = None,
+                40..=47 => self.pending_style.bg = Some(ansi_16_color(param[0] as u8 - 40)),
+                49 => self.pending_style.bg = None,
+                38 => self.pending_style.fg = parse_extended_color(&mut iter),
+                48 => self.pending_style.bg = parse_extended_color(&mut iter),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Consumes the `5;n` (256-color) or `2;r;g;b` (truecolor) sub-sequence that
+/// follows an SGR 38/48 parameter.
+fn parse_extended_color<'a>(
+    iter: &mut impl Iterator<Item = &'a [u16]>,
+) -> Option<(u8, u8, u8)> {
+    match iter.next()?.first().copied()? {
+        2 => {
+            let r = *iter.next()?.first()? as u8;
+            let g = *iter.next()?.first()? as u8;
+            let b = *iter.next()?.first()? as u8;
+            Some((r, g, b))
+        }
+        5 => {
+            let idx = *iter.next()?.first()? as u8;
+            Some(ansi_256_color(idx))
+        }
+        _ => None,
+    }
+}
+
+fn ansi_16_color(idx: u8) -> (u8, u8, u8) {
+    const PALETTE: [(u8, u8, u8); 8] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+    ];
+    PALETTE[(idx % 8) as usize]
+}
+
+fn ansi_256_color(idx: u8) -> (u8, u8, u8) {
+    // Compute the 6x6x6 color cube / grayscale ramp rather than pulling in
+    // a full 256-color table. The bright system colors (8-15) reuse the
+    // normal 8-color palette.
+    match idx {
+        0..=15 => ansi_16_color(idx),
+        16..=231 => {
+            let v = idx - 16;
+            let level = |c: u8| if c == 0 { 0 } else { c * 40 + 55 };
+            (level(v / 36), level((v / 6) % 6), level(v % 6))
+        }
+        _ => {
+            let gray = 8 + (idx - 232) * 10;
+            (gray, gray, gray)
+        }
+    }
+}
+
+impl Perform for TerminalEmulator {
+    fn print(&mut self, c: char) {
+        if self.cursor_col >= self.cols {
+            self.current_line().wrapped = true;
+            self.newline();
+            self.cursor_col = 0;
+        }
+        let style = self.pending_style;
+        let col = self.cursor_col;
+        self.current_line().cells[col] = Cell { text: c, style };
+        self.cursor_col += 1;
+    }
+
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            b'\n' => self.newline(),
+            b'\r' => self.cursor_col = 0,
+            0x08 => self.cursor_col = self.cursor_col.saturating_sub(1),
+            b'\t' => {
+                let next_stop = ((self.cursor_col / 8) + 1) * 8;
+                self.cursor_col = next_stop.min(self.cols.saturating_sub(1));
+            }
+            _ => {}
+        }
+    }
+
+    fn csi_dispatch(
+        &mut self,
+        params: &Params,
+        _intermediates: &[u8],
+        _ignore: bool,
+        action: char,
+    ) {
+        let n = |default: u16| -> u16 {
+            params
+                .iter()
+                .next()
+                .and_then(|p| p.first().copied())
+                .filter(|&v| v != 0)
+                .unwrap_or(default)
+        };
+
+        match action {
+            'A' => self.cursor_row = self.cursor_row.saturating_sub(n(1) as usize),
+            'B' => self.cursor_row = (self.cursor_row + n(1) as usize).min(self.rows - 1),
+            'C' => self.cursor_col = (self.cursor_col + n(1) as usize).min(self.cols - 1),
+            'D' => self.cursor_col = self.cursor_col.saturating_sub(n(1) as usize),
+            'H' | 'f' => {
+                let mut iter = params.iter();
+                let row = iter.next().and_then(|p| p.first().copied()).unwrap_or(1);
+                let col = iter.next().and_then(|p| p.first().copied()).unwrap_or(1);
+                self.cursor_row = (row.max(1) as usize - 1).min(self.rows - 1);
+                self.cursor_col = (col.max(1) as usize - 1).min(self.cols - 1);
+            }
+            'J' => self.erase_in_display(n(0)),
+            'K' => self.erase_in_line(n(0)),
+            'm' => self.apply_sgr(params),
+            _ => {}
+        }
+    }
+}
@@ -1,6 +1,13 @@
+use parking_lot::Mutex;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Runtime};
+
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileEntry {
@@ -25,8 +32,10 @@ pub fn read_directory(path: String) -> Result<Vec<FileEntry>, String> {
 
     let mut entries: Vec<FileEntry> = Vec::new();
 
-    let read_dir = fs::read_dir(dir_path)
-        .map_err(|e| format!("Failed to read directory: {}", e))?;
+    let read_dir = fs::read_dir(dir_path).map_err(|e| {
+        log::warn!("Failed to read directory {}: {}", path, e);
+        format!("Failed to read directory: {}", e)
+    })?;
 
     for entry_result in read_dir {
         let entry = match entry_result {
@@ -34,24 +43,9 @@ pub fn read_directory(path: String) -> Result<Vec<FileEntry>, String> {
             Err(_) => continue, // Skip unreadable entries
         };
 
-        let metadata = match entry.metadata() {
-            Ok(m) => m,
-            Err(_) => continue, // Skip entries with unreadable metadata
-        };
-
-        let name = entry.file_name().to_string_lossy().to_string();
-        let full_path = entry.path().to_string_lossy().to_string();
-        let is_dir = metadata.is_dir();
-        let is_hidden = is_hidden_file(&name, &entry.path());
-        let size = if is_dir { 0 } else { metadata.len() };
-
-        entries.push(FileEntry {
-            name,
-            path: full_path,
-            is_dir,
-            is_hidden,
-            size,
-        });
+        if let Some(file_entry) = file_entry_for(&entry.path()) {
+            entries.push(file_entry);
+        }
     }
 
     // Sort: directories first, then by name (case-insensitive)
@@ -66,6 +60,190 @@ pub fn read_directory(path: String) -> Result<Vec<FileEntry>, String> {
     Ok(entries)
 }
 
+/// Aggregated size of a directory subtree, as reported by `scan_directory_size`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DirSize {
+    pub path: String,
+    pub apparent_bytes: u64,
+    pub disk_bytes: u64,
+    pub entry_count: u64,
+    pub inode_dedup_count: u64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Default)]
+pub struct ScanDirectorySizeOptions {
+    /// When true, every hard-linked entry is counted at full size instead of
+    /// being deduplicated by (dev, inode).
+    #[serde(default)]
+    pub count_hard_links: bool,
+    /// When false (the default), subdirectories on a different device than
+    /// the scan root are skipped instead of descended into.
+    #[serde(default)]
+    pub cross_filesystems: bool,
+}
+
+struct ScanContext {
+    count_hard_links: bool,
+    #[cfg_attr(not(unix), allow(dead_code))]
+    cross_filesystems: bool,
+    #[cfg(unix)]
+    root_dev: Option<u64>,
+    #[cfg(unix)]
+    seen_inodes: Mutex<HashSet<(u64, u64)>>,
+}
+
+#[tauri::command]
+pub fn scan_directory_size<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: String,
+    options: Option<ScanDirectorySizeOptions>,
+) -> Result<DirSize, String> {
+    let options = options.unwrap_or_default();
+    let root_path = Path::new(&path);
+
+    if !root_path.exists() {
+        return Err(format!("Directory does not exist: {}", path));
+    }
+
+    if !root_path.is_dir() {
+        return Err(format!("Path is not a directory: {}", path));
+    }
+
+    #[cfg(unix)]
+    let root_dev = fs::metadata(root_path).ok().map(|m| m.dev());
+
+    let ctx = ScanContext {
+        count_hard_links: options.count_hard_links,
+        cross_filesystems: options.cross_filesystems,
+        #[cfg(unix)]
+        root_dev,
+        #[cfg(unix)]
+        seen_inodes: Mutex::new(HashSet::new()),
+    };
+
+    Ok(scan_dir(&app_handle, &ctx, root_path))
+}
+
+/// Recursively aggregates `path`'s size, fanning out across subdirectories
+/// with rayon so a wide tree scans concurrently instead of one entry at a
+/// time. Emits a `directory-scan-progress` event for each subdirectory as
+/// soon as its own subtree finishes, so a large scan can stream partial
+/// results instead of blocking until everything is done.
+fn scan_dir<R: Runtime>(app_handle: &AppHandle<R>, ctx: &ScanContext, path: &Path) -> DirSize {
+    let mut result = DirSize {
+        path: path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    let read_dir = match fs::read_dir(path) {
+        Ok(rd) => rd,
+        Err(_) => return result, // Unreadable directory: report a zeroed size
+    };
+
+    let mut subdirs: Vec<PathBuf> = Vec::new();
+
+    for entry_result in read_dir {
+        let entry = match entry_result {
+            Ok(e) => e,
+            Err(_) => continue, // Skip unreadable entries
+        };
+
+        let metadata = match entry.metadata() {
+            Ok(m) => m,
+            Err(_) => continue, // Skip entries with unreadable metadata
+        };
+
+        if metadata.is_dir() {
+            #[cfg(unix)]
+            {
+                if !ctx.cross_filesystems {
+                    if let Some(root_dev) = ctx.root_dev {
+                        if metadata.dev() != root_dev {
+                            continue; // Crossed a mount boundary
+                        }
+                    }
+                }
+            }
+            subdirs.push(entry.path());
+            continue;
+        }
+
+        result.entry_count += 1;
+        account_file(ctx, &metadata, &mut result);
+    }
+
+    // Traverse subdirectories concurrently; each one returns its own fully
+    // aggregated DirSize before we fold it into this directory's total.
+    let children: Vec<DirSize> = subdirs
+        .par_iter()
+        .map(|subdir| {
+            let child = scan_dir(app_handle, ctx, subdir);
+            let _ = app_handle.emit("directory-scan-progress", &child);
+            child
+        })
+        .collect();
+
+    for child in children {
+        result.apparent_bytes += child.apparent_bytes;
+        result.disk_bytes += child.disk_bytes;
+        result.entry_count += child.entry_count;
+        result.inode_dedup_count += child.inode_dedup_count;
+    }
+
+    result
+}
+
+fn account_file(ctx: &ScanContext, metadata: &fs::Metadata, result: &mut DirSize) {
+    #[cfg(unix)]
+    {
+        if !ctx.count_hard_links && metadata.nlink() > 1 {
+            let key = (metadata.dev(), metadata.ino());
+            let mut seen = ctx.seen_inodes.lock();
+            if !seen.insert(key) {
+                result.inode_dedup_count += 1;
+                return;
+            }
+        }
+
+        result.apparent_bytes += metadata.len();
+        result.disk_bytes += metadata.blocks() * 512;
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        let _ = ctx; // Hard-link accounting is Unix-only for now
+
+        result.apparent_bytes += metadata.file_size();
+        // `std` has no portable way to read the allocated cluster count on
+        // Windows, so approximate on-disk size by rounding up to the common
+        // 4 KiB NTFS cluster size rather than pulling in a raw WinAPI call.
+        const CLUSTER_SIZE: u64 = 4096;
+        let len = metadata.file_size();
+        result.disk_bytes += len.div_ceil(CLUSTER_SIZE) * CLUSTER_SIZE;
+    }
+}
+
+/// Builds a [`FileEntry`] for a single path, sharing the same
+/// is_dir/is_hidden/size logic `read_directory` uses for each listing row.
+/// Returns `None` if the entry's metadata can't be read (e.g. it was removed
+/// between being listed and being inspected).
+pub(crate) fn file_entry_for(path: &Path) -> Option<FileEntry> {
+    let metadata = fs::metadata(path).ok()?;
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let is_dir = metadata.is_dir();
+    let is_hidden = is_hidden_file(&name, path);
+    let size = if is_dir { 0 } else { metadata.len() };
+
+    Some(FileEntry {
+        name,
+        path: path.to_string_lossy().to_string(),
+        is_dir,
+        is_hidden,
+        size,
+    })
+}
+
 #[tauri::command]
 pub fn get_home_directory() -> Result<String, String> {
     std::env::var("USERPROFILE")
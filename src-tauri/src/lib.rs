@@ -1,8 +1,14 @@
+mod emulator;
 mod filesystem;
+mod logging;
+mod runnable;
 mod terminal;
+mod watcher;
 
 use std::sync::Arc;
+use tauri::Manager;
 use terminal::TerminalManager;
+use watcher::WatcherManager;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -13,13 +19,21 @@ fn greet(name: &str) -> String {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let terminal_manager = Arc::new(TerminalManager::new());
+    let watcher_manager = Arc::new(WatcherManager::new());
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .manage(terminal_manager.clone())
+        .manage(watcher_manager.clone())
+        .setup(|app| {
+            let logger = logging::init(app.handle())?;
+            app.manage(logger);
+            Ok(())
+        })
         .on_window_event(move |_window, event| {
             if let tauri::WindowEvent::Destroyed = event {
                 terminal_manager.close_all();
+                watcher_manager.unwatch_all();
             }
         })
         .invoke_handler(tauri::generate_handler![
@@ -30,9 +44,18 @@ pub fn run() {
             terminal::close_terminal,
             terminal::list_terminals,
             terminal::update_terminal_cwd,
+            terminal::get_terminal_cwd,
+            terminal::get_terminal_screen,
             filesystem::read_directory,
             filesystem::get_home_directory,
-            filesystem::list_drives
+            filesystem::list_drives,
+            filesystem::scan_directory_size,
+            runnable::list_runnables,
+            runnable::spawn_runnable,
+            watcher::watch_directory,
+            watcher::unwatch_directory,
+            logging::set_log_level,
+            logging::get_log_path
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
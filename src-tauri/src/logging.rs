@@ -0,0 +1,142 @@
+//! Structured logging backend: wires the `log` facade to stderr plus a
+//! size-rotated file under the app data directory, so a user can raise
+//! verbosity to debug terminal/PTY issues and attach the log to a bug
+//! report instead of relying on an ad-hoc `eprintln!`.
+
+use log::{LevelFilter, Log, Metadata, Record};
+use parking_lot::Mutex;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, Runtime};
+
+const LOG_FILE_NAME: &str = "agent-monitor.log";
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_ROTATED_FILES: u32 = 3;
+
+pub struct AppLogger {
+    path: PathBuf,
+    file: Mutex<File>,
+    level: Mutex<LevelFilter>,
+}
+
+impl AppLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= *self.level.lock()
+    }
+
+    fn write_record(&self, record: &Record) {
+        let line = format!(
+            "{} [{}] {}: {}\n",
+            chrono::Local::now().to_rfc3339(),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+
+        eprint!("{}", line);
+
+        let mut file = self.file.lock();
+        rotate_if_needed(&self.path, &mut file);
+        let _ = file.write_all(line.as_bytes());
+        let _ = file.flush();
+    }
+
+    pub fn set_level(&self, level: LevelFilter) {
+        *self.level.lock() = level;
+        log::set_max_level(level);
+    }
+
+    pub fn log_path(&self) -> &Path {
+        &self.path
+    }
+}
+
+fn rotate_if_needed(path: &Path, file: &mut File) {
+    let exceeds_cap = file
+        .metadata()
+        .map(|m| m.len() >= MAX_LOG_BYTES)
+        .unwrap_or(false);
+    if !exceeds_cap {
+        return;
+    }
+
+    for i in (1..MAX_ROTATED_FILES).rev() {
+        let from = path.with_extension(format!("log.{}", i));
+        let to = path.with_extension(format!("log.{}", i + 1));
+        let _ = fs::rename(from, to);
+    }
+    let _ = fs::rename(path, path.with_extension("log.1"));
+
+    if let Ok(new_file) = OpenOptions::new().create(true).append(true).open(path) {
+        *file = new_file;
+    }
+}
+
+/// Thin `log::Log` adapter so the shared `Arc<AppLogger>` managed as Tauri
+/// state can also be installed as the global logger.
+struct LoggerHandle(Arc<AppLogger>);
+
+impl Log for LoggerHandle {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.0.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.0.enabled(record.metadata()) {
+            self.0.write_record(record);
+        }
+    }
+
+    fn flush(&self) {
+        let _ = self.0.file.lock().flush();
+    }
+}
+
+/// Opens the rotating log file under the app data directory and installs it
+/// as the global `log` backend. Returns the shared logger so it can also be
+/// managed as Tauri state for the `set_log_level`/`get_log_path` commands.
+pub fn init<R: Runtime>(app_handle: &AppHandle<R>) -> Result<Arc<AppLogger>, String> {
+    let log_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    fs::create_dir_all(&log_dir)
+        .map_err(|e| format!("Failed to create log directory {}: {}", log_dir.display(), e))?;
+
+    let log_path = log_dir.join(LOG_FILE_NAME);
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .map_err(|e| format!("Failed to open log file {}: {}", log_path.display(), e))?;
+
+    let logger = Arc::new(AppLogger {
+        path: log_path,
+        file: Mutex::new(file),
+        level: Mutex::new(LevelFilter::Info),
+    });
+
+    log::set_boxed_logger(Box::new(LoggerHandle(logger.clone())))
+        .map_err(|e| format!("Failed to install logger: {}", e))?;
+    log::set_max_level(LevelFilter::Info);
+
+    Ok(logger)
+}
+
+#[tauri::command]
+pub fn set_log_level<R: Runtime>(app_handle: AppHandle<R>, level: String) -> Result<(), String> {
+    let logger = app_handle.state::<Arc<AppLogger>>();
+    let level_filter: LevelFilter = level
+        .parse()
+        .map_err(|_| format!("Invalid log level: {}", level))?;
+    logger.set_level(level_filter);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_log_path<R: Runtime>(app_handle: AppHandle<R>) -> Result<String, String> {
+    let logger = app_handle.state::<Arc<AppLogger>>();
+    Ok(logger.log_path().to_string_lossy().to_string())
+}
@@ -0,0 +1,101 @@
+//! Project task runner ("runnables"): predefined commands, declared once in
+//! a `runnables.json` file at the root of a project directory, that users
+//! can launch into a managed terminal from the UI instead of retyping build
+//! or test invocations by hand.
+
+use crate::terminal::{SpawnTerminalOptions, TerminalInfo, TerminalManager};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, Runtime};
+
+const RUNNABLES_FILE: &str = "runnables.json";
+const WORKSPACE_DIR_TOKEN: &str = "${workspaceDir}";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Runnable {
+    pub label: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// Parses `<dir>/runnables.json`, replaces `${workspaceDir}` with `dir` in
+/// each entry's `args` and `cwd` (not in `command` or `env`), and resolves a
+/// relative `cwd` against `dir`.
+#[tauri::command]
+pub fn list_runnables(dir: String) -> Result<Vec<Runnable>, String> {
+    let runnables_path = Path::new(&dir).join(RUNNABLES_FILE);
+
+    let contents = fs::read_to_string(&runnables_path)
+        .map_err(|e| format!("Failed to read {}: {}", runnables_path.display(), e))?;
+
+    let runnables: Vec<Runnable> = serde_json::from_str(&contents)
+        .map_err(|e| format!("Malformed {}: {}", RUNNABLES_FILE, e))?;
+
+    Ok(runnables
+        .into_iter()
+        .map(|runnable| resolve_runnable(runnable, &dir))
+        .collect())
+}
+
+fn substitute_workspace_dir(value: &str, dir: &str) -> String {
+    value.replace(WORKSPACE_DIR_TOKEN, dir)
+}
+
+fn resolve_runnable(mut runnable: Runnable, dir: &str) -> Runnable {
+    runnable.args = runnable
+        .args
+        .iter()
+        .map(|arg| substitute_workspace_dir(arg, dir))
+        .collect();
+
+    runnable.cwd = Some(match runnable.cwd {
+        None => dir.to_string(),
+        Some(cwd) => {
+            let cwd = substitute_workspace_dir(&cwd, dir);
+            let cwd_path = Path::new(&cwd);
+            if cwd_path.is_absolute() {
+                cwd
+            } else {
+                Path::new(dir).join(cwd_path).to_string_lossy().to_string()
+            }
+        }
+    });
+
+    runnable
+}
+
+/// Looks up `label` in `<dir>/runnables.json` and launches it into a new
+/// managed terminal via [`TerminalManager::spawn_terminal`], naming the
+/// terminal after the label so its output is recognizable in the UI.
+#[tauri::command]
+pub fn spawn_runnable<R: Runtime>(
+    app_handle: AppHandle<R>,
+    label: String,
+    dir: String,
+) -> Result<TerminalInfo, String> {
+    let runnable = list_runnables(dir.clone())?
+        .into_iter()
+        .find(|r| r.label == label)
+        .ok_or_else(|| format!("No runnable named '{}' in {}", label, dir))?;
+
+    let manager = app_handle.state::<Arc<TerminalManager>>();
+    manager.spawn_terminal(
+        &app_handle,
+        SpawnTerminalOptions {
+            cwd: runnable.cwd,
+            name: Some(runnable.label),
+            command: Some(runnable.command),
+            args: runnable.args,
+            env: runnable.env,
+            ..Default::default()
+        },
+    )
+}
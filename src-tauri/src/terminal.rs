@@ -1,5 +1,6 @@
+use crate::emulator::{TerminalEmulator, TerminalScreen};
 use parking_lot::Mutex;
-use portable_pty::{native_pty_system, CommandBuilder, PtyPair, PtySize};
+use portable_pty::{native_pty_system, Child, CommandBuilder, PtyPair, PtySize};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::{Read, Write};
@@ -24,11 +25,34 @@ pub struct TerminalInfo {
     pub created_at: String,
 }
 
+/// Parameters for [`TerminalManager::spawn_terminal`]. `command`/`args`/`env`
+/// let callers like the runnable subsystem launch something other than a
+/// bare interactive shell while still going through the same PTY, emulator,
+/// and reader-thread setup as every other terminal.
+#[derive(Debug, Default)]
+pub struct SpawnTerminalOptions {
+    pub cwd: Option<String>,
+    pub name: Option<String>,
+    pub source_terminal_id: Option<u32>,
+    pub scrollback_lines: Option<usize>,
+    pub command: Option<String>,
+    pub args: Vec<String>,
+    pub env: HashMap<String, String>,
+}
+
 struct TerminalInstance {
     info: TerminalInfo,
     #[allow(dead_code)]
     pty_pair: PtyPair,
     writer: Box<dyn Write + Send>,
+    pid: Option<u32>,
+    // Owned here rather than by the reader thread, so the child handle lives
+    // as long as the terminal and is dropped when the terminal is closed.
+    #[allow(dead_code)]
+    child: Box<dyn Child + Send + Sync>,
+    // Shared with the reader thread, which feeds it every chunk of raw PTY
+    // output so a newly attached frontend can request a full screen snapshot.
+    emulator: Arc<Mutex<TerminalEmulator>>,
 }
 
 pub struct TerminalManager {
@@ -51,9 +75,18 @@ impl TerminalManager {
     pub fn spawn_terminal<R: Runtime>(
         &self,
         app_handle: &AppHandle<R>,
-        cwd: Option<String>,
-        name: Option<String>,
+        options: SpawnTerminalOptions,
     ) -> Result<TerminalInfo, String> {
+        let SpawnTerminalOptions {
+            cwd,
+            name,
+            source_terminal_id,
+            scrollback_lines,
+            command,
+            args,
+            env,
+        } = options;
+
         // Check for ID overflow
         let current_id = NEXT_TERMINAL_ID.load(Ordering::SeqCst);
         if current_id >= MAX_TERMINAL_ID {
@@ -71,24 +104,39 @@ impl TerminalManager {
             })
             .map_err(|e| format!("Failed to open PTY: {}", e))?;
 
-        let working_dir = cwd.clone().unwrap_or_else(|| {
-            std::env::var("USERPROFILE")
-                .or_else(|_| std::env::var("HOME"))
-                .unwrap_or_else(|_| ".".to_string())
-        });
-
-        let mut cmd = CommandBuilder::new("powershell.exe");
-        cmd.args(["-NoLogo", "-NoExit", "-NoProfile"]);
+        // Default a new pane to wherever the active terminal's shell really
+        // is (not just its last-reported cwd), so "open new pane here"
+        // follows `cd`s the user has already made.
+        let working_dir = cwd
+            .clone()
+            .or_else(|| source_terminal_id.and_then(|id| self.get_terminal_cwd(id).ok()))
+            .unwrap_or_else(|| {
+                std::env::var("USERPROFILE")
+                    .or_else(|_| std::env::var("HOME"))
+                    .unwrap_or_else(|_| ".".to_string())
+            });
+
+        let is_shell = command.is_none();
+        let mut cmd = CommandBuilder::new(command.unwrap_or_else(|| "powershell.exe".to_string()));
+        if is_shell {
+            cmd.args(["-NoLogo", "-NoExit", "-NoProfile"]);
+        } else {
+            cmd.args(&args);
+        }
         cmd.cwd(&working_dir);
 
         // Set environment for better terminal support
         cmd.env("TERM", "xterm-256color");
         cmd.env("COLORTERM", "truecolor");
+        for (key, value) in &env {
+            cmd.env(key, value);
+        }
 
         let child = pty_pair
             .slave
             .spawn_command(cmd)
             .map_err(|e| format!("Failed to spawn command: {}", e))?;
+        let pid = child.process_id();
 
         let id = NEXT_TERMINAL_ID.fetch_add(1, Ordering::SeqCst);
         let terminal_name = name.unwrap_or_else(|| format!("Terminal {}", id));
@@ -110,15 +158,27 @@ impl TerminalManager {
             .try_clone_reader()
             .map_err(|e| format!("Failed to get reader: {}", e))?;
 
+        let emulator = Arc::new(Mutex::new(TerminalEmulator::new(
+            DEFAULT_TERMINAL_ROWS,
+            DEFAULT_TERMINAL_COLS,
+            scrollback_lines,
+        )));
+
         let instance = TerminalInstance {
             info: info.clone(),
             pty_pair,
             writer,
+            pid,
+            child,
+            emulator: emulator.clone(),
         };
 
         self.terminals.lock().insert(id, instance);
+        log::info!("Spawned terminal {} (pid={:?}) in {}", id, pid, working_dir);
 
-        // Spawn reader thread
+        // Spawn reader thread. The child process lives in the TerminalInstance
+        // now, not here, so its handle is dropped when the terminal is removed
+        // from the map (close_terminal) rather than when this thread exits.
         let terminal_id = id;
         let app = app_handle.clone();
         thread::spawn(move || {
@@ -126,11 +186,17 @@ impl TerminalManager {
             loop {
                 match reader.read(&mut buf) {
                     Ok(0) => {
-                        // EOF - terminal closed
+                        log::info!("Terminal {} closed (EOF)", terminal_id);
                         let _ = app.emit("terminal-closed", terminal_id);
                         break;
                     }
                     Ok(n) => {
+                        // Keep streaming raw output for the happy path, and
+                        // also feed the emulator so a reconnecting frontend
+                        // can request a full snapshot instead of replaying
+                        // the whole session.
+                        emulator.lock().feed(&buf[..n]);
+
                         let data = String::from_utf8_lossy(&buf[..n]).to_string();
                         let _ = app.emit(
                             "terminal-output",
@@ -141,15 +207,12 @@ impl TerminalManager {
                         );
                     }
                     Err(e) => {
-                        // Log error (consider using log crate in production)
-                        eprintln!("Error reading from terminal {}: {}", terminal_id, e);
+                        log::error!("Error reading from terminal {}: {}", terminal_id, e);
                         let _ = app.emit("terminal-closed", terminal_id);
                         break;
                     }
                 }
             }
-            // Clean up child process
-            drop(child);
         });
 
         Ok(info)
@@ -191,9 +254,28 @@ impl TerminalManager {
             })
             .map_err(|e| format!("Failed to resize terminal: {}", e))?;
 
+        // Reflow the emulator's grid/scrollback to match, wrapping or
+        // unwrapping lines to the new column count.
+        instance.emulator.lock().resize(rows, cols);
+
         Ok(())
     }
 
+    /// Returns the emulator's current visible grid, plus scrollback when
+    /// requested, so a newly attached or re-rendered frontend can repaint
+    /// without replaying the whole session.
+    pub fn get_terminal_screen(
+        &self,
+        id: u32,
+        include_scrollback: bool,
+    ) -> Result<TerminalScreen, String> {
+        let terminals = self.terminals.lock();
+        let instance = terminals
+            .get(&id)
+            .ok_or_else(|| format!("Terminal {} not found", id))?;
+        Ok(instance.emulator.lock().snapshot(include_scrollback))
+    }
+
     pub fn close_terminal(&self, id: u32) -> Result<(), String> {
         let mut terminals = self.terminals.lock();
         // Removing the terminal will drop the PtyPair, which closes the PTY
@@ -217,6 +299,60 @@ impl TerminalManager {
         instance.info.cwd = cwd;
         Ok(())
     }
+
+    /// Resolve where a terminal's shell process actually is right now and
+    /// record it as the terminal's `TerminalInfo.cwd`, falling back to the
+    /// last-known value when the platform can't (or fails to) report it live.
+    pub fn get_terminal_cwd(&self, id: u32) -> Result<String, String> {
+        let mut terminals = self.terminals.lock();
+        let instance = terminals
+            .get_mut(&id)
+            .ok_or_else(|| format!("Terminal {} not found", id))?;
+
+        if let Some(cwd) = instance.pid.and_then(resolve_process_cwd) {
+            instance.info.cwd = cwd;
+        }
+        Ok(instance.info.cwd.clone())
+    }
+}
+
+/// Best-effort lookup of a process's current working directory, used to keep
+/// `TerminalInfo.cwd` accurate after the shell `cd`s without frontend help.
+#[cfg(target_os = "linux")]
+fn resolve_process_cwd(pid: u32) -> Option<String> {
+    std::fs::read_link(format!("/proc/{}/cwd", pid))
+        .ok()
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn resolve_process_cwd(pid: u32) -> Option<String> {
+    use libproc::libproc::proc_pid::{pidinfo, PIDInfo};
+    use libproc::libproc::vnode_info::VnodePathInfo;
+
+    // PROC_PIDVNODEPATHINFO reports the process's current-directory vnode
+    // path without requiring ptrace-like access to the target process.
+    pidinfo::<VnodePathInfo>(pid as i32, 0)
+        .ok()
+        .map(|info| {
+            let cdir = &info.pvi_cdir.vip_path;
+            let len = cdir.iter().take_while(|&&b| b != 0).count();
+            let bytes: Vec<u8> = cdir[..len].iter().map(|&b| b as u8).collect();
+            String::from_utf8_lossy(&bytes).to_string()
+        })
+}
+
+#[cfg(target_os = "windows")]
+fn resolve_process_cwd(_pid: u32) -> Option<String> {
+    // Windows has no supported API for reading another process's CWD short
+    // of NtQueryInformationProcess/toolhelp snapshots over undocumented
+    // structures; fall back to the caller's last-known value instead.
+    None
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn resolve_process_cwd(_pid: u32) -> Option<String> {
+    None
 }
 
 // Tauri Commands
@@ -225,9 +361,20 @@ pub fn create_terminal<R: Runtime>(
     app_handle: AppHandle<R>,
     cwd: Option<String>,
     name: Option<String>,
+    source_terminal_id: Option<u32>,
+    scrollback_lines: Option<usize>,
 ) -> Result<TerminalInfo, String> {
     let manager = app_handle.state::<Arc<TerminalManager>>();
-    manager.spawn_terminal(&app_handle, cwd, name)
+    manager.spawn_terminal(
+        &app_handle,
+        SpawnTerminalOptions {
+            cwd,
+            name,
+            source_terminal_id,
+            scrollback_lines,
+            ..Default::default()
+        },
+    )
 }
 
 #[tauri::command]
@@ -272,3 +419,19 @@ pub fn update_terminal_cwd<R: Runtime>(
     let manager = app_handle.state::<Arc<TerminalManager>>();
     manager.update_terminal_cwd(id, cwd)
 }
+
+#[tauri::command]
+pub fn get_terminal_cwd<R: Runtime>(app_handle: AppHandle<R>, id: u32) -> Result<String, String> {
+    let manager = app_handle.state::<Arc<TerminalManager>>();
+    manager.get_terminal_cwd(id)
+}
+
+#[tauri::command]
+pub fn get_terminal_screen<R: Runtime>(
+    app_handle: AppHandle<R>,
+    id: u32,
+    include_scrollback: bool,
+) -> Result<TerminalScreen, String> {
+    let manager = app_handle.state::<Arc<TerminalManager>>();
+    manager.get_terminal_screen(id, include_scrollback)
+}
@@ -0,0 +1,171 @@
+//! Live filesystem watching so directory views can refresh automatically
+//! instead of relying on a one-shot `read_directory` snapshot.
+
+use crate::filesystem::{file_entry_for, FileEntry};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Raw events are coalesced over this window so an editor's save storm (a
+/// temp file, a rename, a write) collapses into one `directory-changed` per
+/// affected path, carrying the last event kind seen for that path.
+const DEBOUNCE_WINDOW: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+    Renamed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryChange {
+    pub watched_path: String,
+    pub path: String,
+    pub kind: ChangeKind,
+    pub entry: Option<FileEntry>,
+}
+
+struct WatchHandle {
+    // Held only to keep the watcher (and its OS-level subscription) alive
+    // for as long as this directory is watched.
+    #[allow(dead_code)]
+    watcher: RecommendedWatcher,
+}
+
+#[derive(Default)]
+pub struct WatcherManager {
+    watchers: Mutex<HashMap<String, WatchHandle>>,
+}
+
+impl WatcherManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn watch_directory<R: Runtime>(
+        &self,
+        app_handle: &AppHandle<R>,
+        path: String,
+        recursive: bool,
+    ) -> Result<(), String> {
+        let mut watchers = self.watchers.lock();
+        if watchers.contains_key(&path) {
+            return Ok(()); // Already watching; nothing to do
+        }
+
+        let (tx, rx) = channel::<Event>();
+        let watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            if let Ok(event) = res {
+                let _ = tx.send(event);
+            }
+        })
+        .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+        let mode = if recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+
+        let mut watcher = watcher;
+        watcher
+            .watch(Path::new(&path), mode)
+            .map_err(|e| format!("Failed to watch {}: {}", path, e))?;
+
+        let app = app_handle.clone();
+        let watched_path = path.clone();
+        thread::spawn(move || debounce_loop(app, watched_path, rx));
+
+        watchers.insert(path, WatchHandle { watcher });
+        Ok(())
+    }
+
+    pub fn unwatch_directory(&self, path: &str) -> Result<(), String> {
+        self.watchers
+            .lock()
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| format!("Not watching {}", path))
+    }
+
+    /// Drops every active watcher, stopping their debounce threads (the
+    /// channel disconnects once the watcher side is gone). Called on window
+    /// `Destroyed`, the same cleanup point `TerminalManager` uses.
+    pub fn unwatch_all(&self) {
+        self.watchers.lock().clear();
+    }
+}
+
+fn debounce_loop<R: Runtime>(
+    app: AppHandle<R>,
+    watched_path: String,
+    rx: std::sync::mpsc::Receiver<Event>,
+) {
+    let mut pending: HashMap<PathBuf, EventKind> = HashMap::new();
+    loop {
+        match rx.recv_timeout(DEBOUNCE_WINDOW) {
+            Ok(event) => {
+                for changed_path in event.paths.clone() {
+                    pending.insert(changed_path, event.kind);
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if !pending.is_empty() {
+                    emit_changes(&app, &watched_path, std::mem::take(&mut pending));
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
+
+fn emit_changes<R: Runtime>(
+    app: &AppHandle<R>,
+    watched_path: &str,
+    pending: HashMap<PathBuf, EventKind>,
+) {
+    for (path, kind) in pending {
+        let change = DirectoryChange {
+            watched_path: watched_path.to_string(),
+            path: path.to_string_lossy().to_string(),
+            kind: classify(kind),
+            entry: file_entry_for(&path),
+        };
+        let _ = app.emit("directory-changed", change);
+    }
+}
+
+fn classify(kind: EventKind) -> ChangeKind {
+    match kind {
+        EventKind::Create(_) => ChangeKind::Created,
+        EventKind::Remove(_) => ChangeKind::Removed,
+        EventKind::Modify(notify::event::ModifyKind::Name(_)) => ChangeKind::Renamed,
+        _ => ChangeKind::Modified,
+    }
+}
+
+#[tauri::command]
+pub fn watch_directory<R: Runtime>(
+    app_handle: AppHandle<R>,
+    path: String,
+    recursive: Option<bool>,
+) -> Result<(), String> {
+    let manager = app_handle.state::<Arc<WatcherManager>>();
+    manager.watch_directory(&app_handle, path, recursive.unwrap_or(true))
+}
+
+#[tauri::command]
+pub fn unwatch_directory<R: Runtime>(app_handle: AppHandle<R>, path: String) -> Result<(), String> {
+    let manager = app_handle.state::<Arc<WatcherManager>>();
+    manager.unwatch_directory(&path)
+}